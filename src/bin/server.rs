@@ -0,0 +1,15 @@
+use std::env;
+use std::process;
+use transaction_engine::server::serve;
+use transaction_engine::ProcessingContext;
+
+fn main() {
+    env_logger::init();
+    let addr = env::args()
+        .nth(1)
+        .unwrap_or_else(|| "127.0.0.1:7878".to_string());
+    if let Err(err) = serve(&addr, ProcessingContext::default()) {
+        println!("{}", err);
+        process::exit(1);
+    }
+}
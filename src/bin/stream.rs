@@ -0,0 +1,16 @@
+use std::io;
+use std::process;
+use transaction_engine::{run_stream, ProcessingContext};
+
+fn main() {
+    env_logger::init();
+    let result = run_stream(
+        Box::new(io::stdin()),
+        Box::new(io::stdout()),
+        &ProcessingContext::default(),
+    );
+    if let Err(err) = result {
+        println!("{}", err);
+        process::exit(1);
+    }
+}
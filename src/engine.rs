@@ -0,0 +1,162 @@
+use crate::{AccountStatus, Client, Input, LedgerError, ProcessingContext, Transaction};
+use csv::{ReaderBuilder, Trim};
+use log::error;
+use std::collections::HashMap;
+use std::error::Error;
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::{BufReader, Read, Write};
+
+/// Drives the domain model from a stream of `Input` records, one at a time,
+/// and exposes account snapshots on demand. This decouples "apply a
+/// transaction" from any particular input/output source, so a file, stdin,
+/// or an HTTP request can all feed the same `Engine`.
+pub struct Engine {
+    clients: HashMap<u16, Client>,
+    context: ProcessingContext,
+}
+
+impl Engine {
+    pub fn new(context: ProcessingContext) -> Engine {
+        Engine {
+            clients: HashMap::new(),
+            context,
+        }
+    }
+
+    /// Parses and applies a single record, returning the `LedgerError` if
+    /// the record was malformed or the ledger operation was rejected.
+    /// Callers that want `process_file`'s skip-and-continue behavior can
+    /// just log the returned error and move on.
+    pub fn apply(&mut self, input: Input) -> Result<(), LedgerError> {
+        let client_id = input.client_id;
+        let transaction = Transaction::try_from(input)?;
+
+        let client = self
+            .clients
+            .entry(client_id)
+            .or_insert_with(|| Client::new(client_id));
+
+        match transaction {
+            Transaction::Deposit {
+                transaction_id,
+                amount,
+            } => client.deposit(transaction_id, amount),
+            Transaction::Withdrawal {
+                transaction_id,
+                amount,
+            } => client.withdrawal(transaction_id, amount),
+            Transaction::Dispute { transaction_id } => {
+                client.dispute(transaction_id, &self.context)
+            }
+            Transaction::Resolve { transaction_id } => {
+                client.resolve(transaction_id, &self.context)
+            }
+            Transaction::Chargeback { transaction_id } => {
+                client.chargeback(transaction_id, &self.context)
+            }
+        }
+    }
+
+    /// A snapshot of one client's current balances, rounded for display.
+    /// Returns `None` if no well-formed record for the client has reached
+    /// `apply`; a client whose records were all rejected by the ledger still
+    /// has a snapshot.
+    pub fn account_status(&self, client_id: u16) -> Option<AccountStatus> {
+        self.clients.get(&client_id).map(snapshot)
+    }
+
+    /// Snapshots of every account seen so far, in no particular order.
+    pub fn account_statuses(&self) -> Vec<AccountStatus> {
+        self.clients.values().map(snapshot).collect()
+    }
+}
+
+fn snapshot(client: &Client) -> AccountStatus {
+    let mut status = client.account_status.clone();
+    status.round_and_normalize();
+    status
+}
+
+/// Feeds an `Engine` from a CSV file and serializes every account's final
+/// state once the file is exhausted. A thin wrapper around `Engine` kept
+/// for the existing one-shot batch use case.
+pub fn process_file(
+    file_path: OsString,
+    out: Box<dyn Write>,
+    context: &ProcessingContext,
+) -> Result<(), Box<dyn Error>> {
+    let file = File::open(file_path)?;
+
+    let rdr = ReaderBuilder::new()
+        .flexible(true)
+        .trim(Trim::All)
+        .from_reader(BufReader::new(file));
+
+    let mut engine = Engine::new(*context);
+    apply_all(rdr, &mut engine);
+
+    let mut wtr = csv::Writer::from_writer(out);
+    for status in engine.account_statuses() {
+        wtr.serialize(status)?;
+    }
+
+    Ok(())
+}
+
+/// Reads newline-delimited CSV transactions from `input` and, as soon as
+/// each one is applied, writes that client's current account snapshot to
+/// `out` — unlike `process_file`, a result becomes visible without ever
+/// waiting for EOF.
+pub fn run_stream(
+    input: Box<dyn Read>,
+    out: Box<dyn Write>,
+    context: &ProcessingContext,
+) -> Result<(), Box<dyn Error>> {
+    let mut rdr = ReaderBuilder::new()
+        .flexible(true)
+        .trim(Trim::All)
+        .from_reader(input);
+
+    let mut wtr = csv::Writer::from_writer(out);
+    let mut engine = Engine::new(*context);
+
+    for result in rdr.deserialize() {
+        match result {
+            Ok(record) => {
+                let record: Input = record;
+                let client_id = record.client_id;
+
+                if let Err(err) = engine.apply(record) {
+                    error!("ledger error: {}", err);
+                }
+
+                if let Some(status) = engine.account_status(client_id) {
+                    wtr.serialize(status)?;
+                    wtr.flush()?;
+                }
+            }
+            Err(err) => {
+                error!("parsing error: {}", err);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn apply_all(mut rdr: csv::Reader<BufReader<File>>, engine: &mut Engine) {
+    for result in rdr.deserialize() {
+        match result {
+            Ok(record) => {
+                let record: Input = record;
+                if let Err(err) = engine.apply(record) {
+                    error!("ledger error: {}", err);
+                }
+            }
+            Err(err) => {
+                error!("parsing error: {}", err);
+            }
+        }
+    }
+}
@@ -1,4 +1,3 @@
-use csv::{ReaderBuilder, Trim};
 use log::error;
 use rust_decimal::Decimal;
 use serde::{de, Deserialize, Deserializer, Serialize};
@@ -7,11 +6,14 @@ use std::env;
 use std::error::Error;
 use std::ffi::OsString;
 use std::fmt::Display;
-use std::fs::File;
-use std::io::{BufReader, Write};
-use std::ops::{Add, Sub};
+use std::ops::{Add, Mul, Sub};
 use std::str::FromStr;
 
+mod engine;
+pub mod server;
+
+pub use engine::{process_file, run_stream, Engine};
+
 #[derive(Debug, Deserialize)]
 pub struct Input {
     #[serde(rename = "type")]
@@ -24,26 +26,104 @@ pub struct Input {
     pub amount: Option<Decimal>,
 }
 
+/// A parsed, validated transaction. Deposits and withdrawals carry their
+/// amount; dispute/resolve/chargeback only ever refer back to an earlier
+/// transaction by id, so they carry none.
 #[derive(Debug, Clone)]
-pub struct Transaction {
-    pub transaction_id: u32,
-    pub operation: Operation,
-    pub amount: Option<Decimal>,
-    pub is_disputed: bool,
+pub enum Transaction {
+    Deposit {
+        transaction_id: u32,
+        amount: Decimal,
+    },
+    Withdrawal {
+        transaction_id: u32,
+        amount: Decimal,
+    },
+    Dispute {
+        transaction_id: u32,
+    },
+    Resolve {
+        transaction_id: u32,
+    },
+    Chargeback {
+        transaction_id: u32,
+    },
 }
 
 impl Transaction {
-    pub fn from_input(input: Input) -> Transaction {
-        Transaction {
-            transaction_id: input.transaction_id,
-            operation: input.operation,
-            amount: input.amount,
-            is_disputed: false,
+    pub fn transaction_id(&self) -> u32 {
+        match *self {
+            Transaction::Deposit { transaction_id, .. }
+            | Transaction::Withdrawal { transaction_id, .. }
+            | Transaction::Dispute { transaction_id }
+            | Transaction::Resolve { transaction_id }
+            | Transaction::Chargeback { transaction_id } => transaction_id,
         }
     }
+}
 
-    pub fn set_dispute(&mut self, is_disputed: bool) {
-        self.is_disputed = is_disputed;
+impl TryFrom<Input> for Transaction {
+    type Error = LedgerError;
+
+    fn try_from(input: Input) -> Result<Self, Self::Error> {
+        let transaction_id = input.transaction_id;
+        match input.operation {
+            Operation::Deposit => Ok(Transaction::Deposit {
+                transaction_id,
+                amount: input.amount.ok_or(LedgerError::MissingAmount)?,
+            }),
+            Operation::Withdrawal => Ok(Transaction::Withdrawal {
+                transaction_id,
+                amount: input.amount.ok_or(LedgerError::MissingAmount)?,
+            }),
+            Operation::Dispute => {
+                if input.amount.is_some() {
+                    return Err(LedgerError::UnexpectedAmount);
+                }
+                Ok(Transaction::Dispute { transaction_id })
+            }
+            Operation::Resolve => {
+                if input.amount.is_some() {
+                    return Err(LedgerError::UnexpectedAmount);
+                }
+                Ok(Transaction::Resolve { transaction_id })
+            }
+            Operation::Chargeback => {
+                if input.amount.is_some() {
+                    return Err(LedgerError::UnexpectedAmount);
+                }
+                Ok(Transaction::Chargeback { transaction_id })
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum BookedKind {
+    Deposit,
+    Withdrawal,
+}
+
+// Legal transitions: Processed -> Disputed -> Resolved | ChargedBack.
+// Once a tx reaches Resolved or ChargedBack it is terminal. Each state
+// carries the kind of the booked transaction it belongs to, so a client
+// never needs to keep the rest of the original transaction around.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TxState {
+    Processed(BookedKind),
+    Disputed(BookedKind),
+    Resolved(BookedKind),
+    ChargedBack(BookedKind),
+}
+
+impl TxState {
+    pub fn kind(self) -> BookedKind {
+        match self {
+            TxState::Processed(kind)
+            | TxState::Disputed(kind)
+            | TxState::Resolved(kind)
+            | TxState::ChargedBack(kind) => kind,
+        }
     }
 }
 
@@ -58,7 +138,7 @@ pub enum Operation {
 }
 
 // CSV output model
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct AccountStatus {
     #[serde(rename = "client")]
     client_id: u16,
@@ -68,16 +148,229 @@ pub struct AccountStatus {
     pub locked: bool,
 }
 
+/// Errors returned by `Client` ledger operations and, for a record whose
+/// amount does not fit its type (`MissingAmount`/`UnexpectedAmount`), by
+/// `Transaction::try_from`. `process_file` logs and skips these rather than
+/// aborting the whole run.
+#[derive(Debug, PartialEq, Eq)]
+pub enum LedgerError {
+    NotEnoughFunds,
+    UnknownTx(u16, u32),
+    MissingAmount,
+    UnexpectedAmount,
+    AlreadyDisputed,
+    NotDisputed,
+    FrozenAccount,
+    NotDisputable,
+    NegativeBalance,
+}
+
+impl Display for LedgerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LedgerError::NotEnoughFunds => write!(f, "not enough available funds"),
+            LedgerError::UnknownTx(client_id, tx_id) => {
+                write!(f, "unknown tx {} for client {}", tx_id, client_id)
+            }
+            LedgerError::MissingAmount => write!(f, "transaction is missing an amount"),
+            LedgerError::UnexpectedAmount => {
+                write!(f, "transaction must not carry an amount")
+            }
+            LedgerError::AlreadyDisputed => write!(f, "transaction is already disputed"),
+            LedgerError::NotDisputed => write!(f, "transaction is not currently disputed"),
+            LedgerError::FrozenAccount => write!(f, "account is frozen"),
+            LedgerError::NotDisputable => {
+                write!(
+                    f,
+                    "transaction kind is not disputable under the current policy"
+                )
+            }
+            LedgerError::NegativeBalance => {
+                write!(f, "dispute would drive available or held funds negative")
+            }
+        }
+    }
+}
+
+impl Error for LedgerError {}
+
+/// A signed multiple of a transaction's amount applied to one balance field
+/// when a dispute-related operation is processed, e.g. `-1` subtracts the
+/// full amount and `0` leaves the field untouched.
+#[derive(Debug, Clone, Copy)]
+pub struct BalanceDeltas {
+    pub available: i8,
+    pub held: i8,
+    pub total: i8,
+}
+
+impl BalanceDeltas {
+    fn apply(
+        self,
+        status: &mut AccountStatus,
+        amount: Decimal,
+        reject_negative: bool,
+    ) -> Result<(), LedgerError> {
+        let new_available = status
+            .available
+            .add(Decimal::from(self.available).mul(amount));
+        let new_held = status.held.add(Decimal::from(self.held).mul(amount));
+
+        if reject_negative && (new_available < Decimal::new(0, 0) || new_held < Decimal::new(0, 0))
+        {
+            return Err(LedgerError::NegativeBalance);
+        }
+
+        status.available = new_available;
+        status.held = new_held;
+        status.total = status.total.add(Decimal::from(self.total).mul(amount));
+        Ok(())
+    }
+}
+
+/// Controls which transaction kinds can be disputed and the exact
+/// `available`/`held`/`total` deltas a dispute/resolve/chargeback applies.
+/// The defaults reproduce the ledger's original, unconditional behavior: any
+/// booked transaction is disputable, and a disputed deposit moves its amount
+/// from `available` into `held` while a disputed withdrawal (whose amount
+/// was already subtracted from `available`/`total` when it was processed)
+/// instead restores it into `held` and `total`, reflecting the claim that
+/// the withdrawal shouldn't have happened; `resolve` undoes exactly what
+/// `dispute` did and `chargeback` makes the reversal permanent.
+#[derive(Debug, Clone, Copy)]
+pub struct DisputePolicy {
+    pub deposit_disputable: bool,
+    pub withdrawal_disputable: bool,
+    /// When `true`, a dispute that would drive `available` or `held`
+    /// negative is rejected with `LedgerError::NegativeBalance` instead of
+    /// being applied.
+    pub reject_negative_balances: bool,
+    pub deposit_dispute: BalanceDeltas,
+    pub deposit_resolve: BalanceDeltas,
+    pub deposit_chargeback: BalanceDeltas,
+    pub withdrawal_dispute: BalanceDeltas,
+    pub withdrawal_resolve: BalanceDeltas,
+    pub withdrawal_chargeback: BalanceDeltas,
+}
+
+impl DisputePolicy {
+    fn is_disputable(&self, kind: BookedKind) -> bool {
+        match kind {
+            BookedKind::Deposit => self.deposit_disputable,
+            BookedKind::Withdrawal => self.withdrawal_disputable,
+        }
+    }
+
+    fn dispute_deltas(&self, kind: BookedKind) -> BalanceDeltas {
+        match kind {
+            BookedKind::Deposit => self.deposit_dispute,
+            BookedKind::Withdrawal => self.withdrawal_dispute,
+        }
+    }
+
+    fn resolve_deltas(&self, kind: BookedKind) -> BalanceDeltas {
+        match kind {
+            BookedKind::Deposit => self.deposit_resolve,
+            BookedKind::Withdrawal => self.withdrawal_resolve,
+        }
+    }
+
+    fn chargeback_deltas(&self, kind: BookedKind) -> BalanceDeltas {
+        match kind {
+            BookedKind::Deposit => self.deposit_chargeback,
+            BookedKind::Withdrawal => self.withdrawal_chargeback,
+        }
+    }
+}
+
+impl Default for DisputePolicy {
+    fn default() -> Self {
+        DisputePolicy {
+            deposit_disputable: true,
+            withdrawal_disputable: true,
+            reject_negative_balances: false,
+            deposit_dispute: BalanceDeltas {
+                available: -1,
+                held: 1,
+                total: 0,
+            },
+            deposit_resolve: BalanceDeltas {
+                available: 1,
+                held: -1,
+                total: 0,
+            },
+            deposit_chargeback: BalanceDeltas {
+                available: 0,
+                held: -1,
+                total: -1,
+            },
+            withdrawal_dispute: BalanceDeltas {
+                available: 0,
+                held: 1,
+                total: 1,
+            },
+            withdrawal_resolve: BalanceDeltas {
+                available: 0,
+                held: -1,
+                total: -1,
+            },
+            withdrawal_chargeback: BalanceDeltas {
+                available: 1,
+                held: -1,
+                total: 0,
+            },
+        }
+    }
+}
+
+/// Runtime options for a processing run: how a locked (charged-back)
+/// account handles further dispute-related operations, whether terminal
+/// transactions are evicted, and the dispute policy.
+#[derive(Debug, Clone, Copy)]
+pub struct ProcessingContext {
+    /// When `true`, disputes/resolves/chargebacks against a transaction
+    /// already booked on a frozen account are still allowed, so in-flight
+    /// disputes can be unwound. Deposits and withdrawals are always
+    /// rejected on a frozen account regardless of this flag.
+    pub allow_dispute_ops_when_frozen: bool,
+    /// When `true`, a transaction's stored amount/state are dropped as
+    /// soon as it reaches a terminal `Resolved`/`ChargedBack` state,
+    /// bounding memory use on inputs with many one-off disputes. A later
+    /// dispute/resolve/chargeback of an evicted tx then fails with
+    /// `UnknownTx` rather than `AlreadyDisputed`/`NotDisputed`.
+    pub evict_terminal_transactions: bool,
+    /// Which transaction kinds are disputable and what balance deltas a
+    /// dispute/resolve/chargeback applies.
+    pub dispute_policy: DisputePolicy,
+}
+
+impl Default for ProcessingContext {
+    fn default() -> Self {
+        ProcessingContext {
+            allow_dispute_ops_when_frozen: true,
+            evict_terminal_transactions: false,
+            dispute_policy: DisputePolicy::default(),
+        }
+    }
+}
+
+/// Holds an account's balances plus the minimal per-transaction state
+/// needed to later service a dispute. Transaction amounts and lifecycle
+/// states are tracked in separate, compact maps rather than cloning full
+/// `Transaction`s, so memory use stays bounded to two `Decimal`/`TxState`
+/// entries per still-relevant transaction instead of the whole record.
 pub struct Client {
     pub account_status: AccountStatus,
-    pub transactions: HashMap<u32, Transaction>,
+    tx_amounts: HashMap<u32, Decimal>,
+    tx_states: HashMap<u32, TxState>,
 }
 
 impl Client {
     pub fn new(client_id: u16) -> Client {
         Client {
             account_status: AccountStatus::new(client_id),
-            transactions: HashMap::new(),
+            tx_amounts: HashMap::new(),
+            tx_states: HashMap::new(),
         }
     }
 
@@ -85,129 +378,144 @@ impl Client {
         self.account_status
     }
 
-    pub fn deposit(&mut self, transaction: Transaction) {
-        match transaction.amount {
-            Some(amount) => {
-                self.account_status.available = self.account_status.available.add(amount);
-                self.account_status.total = self.account_status.total.add(amount);
+    fn book(&mut self, transaction_id: u32, amount: Decimal, kind: BookedKind) {
+        self.tx_amounts.insert(transaction_id, amount);
+        self.tx_states
+            .insert(transaction_id, TxState::Processed(kind));
+    }
 
-                self.transactions
-                    .insert(transaction.transaction_id, transaction);
-            }
-            None => error!("invalid deposit - no amount for tx {:?}", transaction),
+    /// Advances a booked transaction's state, evicting it entirely once it
+    /// reaches a terminal state if the context asks for that.
+    fn transition(&mut self, transaction_id: u32, state: TxState, context: &ProcessingContext) {
+        if context.evict_terminal_transactions
+            && matches!(state, TxState::Resolved(_) | TxState::ChargedBack(_))
+        {
+            self.tx_amounts.remove(&transaction_id);
+            self.tx_states.remove(&transaction_id);
+        } else {
+            self.tx_states.insert(transaction_id, state);
         }
     }
 
-    pub fn withdrawal(&mut self, transaction: Transaction) {
-        match transaction.amount {
-            Some(amount) => {
-                if self.account_status.available > amount {
-                    self.account_status.available = self.account_status.available.sub(amount);
-                    self.account_status.total = self.account_status.total.sub(amount);
-
-                    self.transactions
-                        .insert(transaction.transaction_id, transaction);
-                } else {
-                    error!(
-                        "invalid withdrawal - not enough funds for tx {:?}",
-                        transaction
-                    );
-                }
-            }
-            None => error!("invalid withdrawal - no amount for tx {:?}", transaction),
+    pub fn deposit(&mut self, transaction_id: u32, amount: Decimal) -> Result<(), LedgerError> {
+        if self.account_status.locked {
+            return Err(LedgerError::FrozenAccount);
         }
+
+        self.account_status.available = self.account_status.available.add(amount);
+        self.account_status.total = self.account_status.total.add(amount);
+
+        self.book(transaction_id, amount, BookedKind::Deposit);
+        Ok(())
     }
 
-    pub fn dispute(&mut self, transaction: Transaction) {
-        match self.transactions.get_mut(&transaction.transaction_id) {
-            Some(disputed) => match disputed.amount {
-                Some(amount) => {
-                    disputed.set_dispute(true);
-                    match disputed.operation {
-                        Operation::Deposit => {
-                            self.account_status.available =
-                                self.account_status.available.sub(amount);
-                            self.account_status.held = self.account_status.held.add(amount);
-                        }
-                        Operation::Withdrawal => {
-                            self.account_status.held = self.account_status.held.add(amount);
-                            self.account_status.total = self.account_status.total.add(amount);
-                        }
-                        _ => error!("invalid dispute - bad operation - for tx: {:?} and alleged dispute tx: {:?}", transaction, disputed)
-                    }
-                }
-                None => error!(
-                    "invalid dispute - no amount - for tx: {:?} and alleged dispute tx: {:?}",
-                    transaction, disputed
-                ),
-            },
-            None => error!(
-                "invalid dispute - disputed tx not found - for tx: {:?}",
-                transaction
-            ),
+    pub fn withdrawal(&mut self, transaction_id: u32, amount: Decimal) -> Result<(), LedgerError> {
+        if self.account_status.locked {
+            return Err(LedgerError::FrozenAccount);
+        }
+
+        if self.account_status.available <= amount {
+            return Err(LedgerError::NotEnoughFunds);
         }
+
+        self.account_status.available = self.account_status.available.sub(amount);
+        self.account_status.total = self.account_status.total.sub(amount);
+
+        self.book(transaction_id, amount, BookedKind::Withdrawal);
+        Ok(())
     }
 
-    pub fn resolve(&mut self, transaction: Transaction) {
-        match self.transactions.get_mut(&transaction.transaction_id) {
-            Some(disputed) => {
-                if disputed.is_disputed {
-                    match disputed.amount {
-                        Some(amount) => match disputed.operation {
-                            Operation::Deposit => {
-                                self.account_status.available =
-                                    self.account_status.available.add(amount);
-                                self.account_status.held = self.account_status.held.sub(amount);
-                            }
-                            Operation::Withdrawal => {
-                                self.account_status.held = self.account_status.held.sub(amount);
-                                self.account_status.total = self.account_status.total.sub(amount);
-                            }
-                            _ => error!("invalid resolve - bad operation - for tx: {:?} and alleged dispute tx: {:?}", transaction, disputed)
-                        },
-                        None => error!("invalid resolve - no amount - for tx: {:?} and alleged dispute tx: {:?}", transaction, disputed)
-                    }
-                } else {
-                    error!("invalid resolve - alleged dispute not disputed - for tx: {:?} and alleged dispute tx: {:?}", transaction, disputed)
+    pub fn dispute(
+        &mut self,
+        transaction_id: u32,
+        context: &ProcessingContext,
+    ) -> Result<(), LedgerError> {
+        if self.account_status.locked && !context.allow_dispute_ops_when_frozen {
+            return Err(LedgerError::FrozenAccount);
+        }
+
+        let client_id = self.account_status.client_id();
+        match self.tx_states.get(&transaction_id).copied() {
+            Some(TxState::Processed(kind)) => {
+                let policy = &context.dispute_policy;
+                if !policy.is_disputable(kind) {
+                    return Err(LedgerError::NotDisputable);
                 }
+                let amount = *self
+                    .tx_amounts
+                    .get(&transaction_id)
+                    .expect("tx_amounts and tx_states are kept in sync");
+                policy.dispute_deltas(kind).apply(
+                    &mut self.account_status,
+                    amount,
+                    policy.reject_negative_balances,
+                )?;
+                self.transition(transaction_id, TxState::Disputed(kind), context);
+                Ok(())
             }
-            None => error!(
-                "invalid resolve - disputed tx not found - for tx: {:?}",
-                transaction
-            ),
+            Some(_) => Err(LedgerError::AlreadyDisputed),
+            None => Err(LedgerError::UnknownTx(client_id, transaction_id)),
         }
     }
 
-    pub fn chargeback(&mut self, transaction: Transaction) {
-        match self.transactions.get_mut(&transaction.transaction_id) {
-            Some(disputed) => {
-                if disputed.is_disputed {
-                    match disputed.amount {
-                        Some(amount) => {
-                            self.account_status.locked = true;
-                            match disputed.operation {
-                                Operation::Deposit => {
-                                    self.account_status.held = self.account_status.held.sub(amount);
-                                    self.account_status.total = self.account_status.total.sub(amount);
-                                }
-                                Operation::Withdrawal => {
-                                    self.account_status.available =
-                                        self.account_status.available.add(amount);
-                                    self.account_status.held = self.account_status.held.sub(amount);
-                                }
-                                _ => error!("invalid chargeback - bad operation - for tx: {:?} and alleged dispute tx: {:?}", transaction, disputed)
-                            }
-                        },
-                        None => error!("invalid chargeback - no amount - for tx: {:?} and alleged dispute tx: {:?}", transaction, disputed)
-                    }
-                } else {
-                    error!("invalid chargeback - alleged dispute not disputed - for tx: {:?} and alleged dispute tx: {:?}", transaction, disputed)
-                }
+    pub fn resolve(
+        &mut self,
+        transaction_id: u32,
+        context: &ProcessingContext,
+    ) -> Result<(), LedgerError> {
+        if self.account_status.locked && !context.allow_dispute_ops_when_frozen {
+            return Err(LedgerError::FrozenAccount);
+        }
+
+        let client_id = self.account_status.client_id();
+        match self.tx_states.get(&transaction_id).copied() {
+            Some(TxState::Disputed(kind)) => {
+                let policy = &context.dispute_policy;
+                let amount = *self
+                    .tx_amounts
+                    .get(&transaction_id)
+                    .expect("tx_amounts and tx_states are kept in sync");
+                policy.resolve_deltas(kind).apply(
+                    &mut self.account_status,
+                    amount,
+                    policy.reject_negative_balances,
+                )?;
+                self.transition(transaction_id, TxState::Resolved(kind), context);
+                Ok(())
+            }
+            Some(_) => Err(LedgerError::NotDisputed),
+            None => Err(LedgerError::UnknownTx(client_id, transaction_id)),
+        }
+    }
+
+    pub fn chargeback(
+        &mut self,
+        transaction_id: u32,
+        context: &ProcessingContext,
+    ) -> Result<(), LedgerError> {
+        if self.account_status.locked && !context.allow_dispute_ops_when_frozen {
+            return Err(LedgerError::FrozenAccount);
+        }
+
+        let client_id = self.account_status.client_id();
+        match self.tx_states.get(&transaction_id).copied() {
+            Some(TxState::Disputed(kind)) => {
+                let policy = &context.dispute_policy;
+                let amount = *self
+                    .tx_amounts
+                    .get(&transaction_id)
+                    .expect("tx_amounts and tx_states are kept in sync");
+                policy.chargeback_deltas(kind).apply(
+                    &mut self.account_status,
+                    amount,
+                    policy.reject_negative_balances,
+                )?;
+                self.account_status.locked = true;
+                self.transition(transaction_id, TxState::ChargedBack(kind), context);
+                Ok(())
             }
-            None => error!(
-                "invalid chargeback - disputed tx not found - for tx: {:?}",
-                transaction
-            ),
+            Some(_) => Err(LedgerError::NotDisputed),
+            None => Err(LedgerError::UnknownTx(client_id, transaction_id)),
         }
     }
 }
@@ -223,6 +531,10 @@ impl AccountStatus {
         }
     }
 
+    pub fn client_id(&self) -> u16 {
+        self.client_id
+    }
+
     pub fn round_and_normalize(&mut self) {
         self.available = self.available.round_dp(4).normalize();
         self.held = self.held.round_dp(4).normalize();
@@ -272,60 +584,9 @@ fn get_first_arg() -> Result<OsString, Box<dyn Error>> {
 pub fn run() -> Result<(), Box<dyn Error>> {
     env_logger::init();
     let file_path = get_first_arg()?;
-    process_file(file_path, Box::new(std::io::stdout()))
-}
-
-pub fn process_file(file_path: OsString, out: Box<dyn Write>) -> Result<(), Box<dyn Error>> {
-    let file = File::open(file_path)?;
-
-    let mut rdr = ReaderBuilder::new()
-        .flexible(true)
-        .trim(Trim::All)
-        .from_reader(BufReader::new(file));
-
-    let mut clients: HashMap<u16, Client> = HashMap::new();
-
-    for result in rdr.deserialize() {
-        match result {
-            Ok(record) => {
-                let record: Input = record;
-
-                let client = clients
-                    .entry(record.client_id)
-                    .or_insert(Client::new(record.client_id));
-
-                let transaction = Transaction::from_input(record);
-
-                match transaction.operation {
-                    Operation::Deposit => {
-                        client.deposit(transaction);
-                    }
-                    Operation::Withdrawal => {
-                        client.withdrawal(transaction);
-                    }
-                    Operation::Dispute => {
-                        client.dispute(transaction);
-                    }
-                    Operation::Resolve => {
-                        client.resolve(transaction);
-                    }
-                    Operation::Chargeback => {
-                        client.chargeback(transaction);
-                    }
-                }
-            }
-            Err(err) => {
-                error!("parsing error: {}", err);
-            }
-        }
-    }
-
-    let mut wtr = csv::Writer::from_writer(out);
-
-    for (_, mut client) in clients.drain() {
-        client.account_status.round_and_normalize();
-        wtr.serialize(client.into_account_status())?;
-    }
-
-    Ok(())
+    process_file(
+        file_path,
+        Box::new(std::io::stdout()),
+        &ProcessingContext::default(),
+    )
 }
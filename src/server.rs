@@ -0,0 +1,82 @@
+use crate::{Engine, Input, ProcessingContext};
+use log::{error, info};
+use std::error::Error;
+use std::io::Cursor;
+use std::sync::Mutex;
+use tiny_http::{Method, Response, Server};
+
+/// A minimal HTTP front-end over an `Engine`. `POST /transactions` applies
+/// one transaction (a JSON body with the same fields as a CSV row);
+/// `GET /accounts/{client_id}` returns that client's current snapshot as
+/// JSON. Requests are served one at a time from a single `Engine` behind a
+/// mutex — this is a thin adapter over the engine, not a scalability story.
+pub fn serve(addr: &str, context: ProcessingContext) -> Result<(), Box<dyn Error>> {
+    let server = Server::http(addr).map_err(|err| format!("failed to bind {}: {}", addr, err))?;
+    let engine = Mutex::new(Engine::new(context));
+
+    info!("listening on {}", addr);
+
+    for mut request in server.incoming_requests() {
+        let response = match (request.method(), request.url()) {
+            (&Method::Post, "/transactions") => {
+                let mut body = String::new();
+                match request.as_reader().read_to_string(&mut body) {
+                    Ok(_) => handle_post_transaction(&engine, &body),
+                    Err(err) => {
+                        error!("failed to read request body: {}", err);
+                        Response::from_string("invalid request body").with_status_code(400)
+                    }
+                }
+            }
+            (&Method::Get, url) if url.starts_with("/accounts/") => {
+                let client_id = url.trim_start_matches("/accounts/").parse::<u16>().ok();
+                handle_get_account(&engine, client_id)
+            }
+            _ => Response::from_string("not found").with_status_code(404),
+        };
+
+        if let Err(err) = request.respond(response) {
+            error!("failed to send response: {}", err);
+        }
+    }
+
+    Ok(())
+}
+
+fn handle_post_transaction(engine: &Mutex<Engine>, body: &str) -> Response<Cursor<Vec<u8>>> {
+    match serde_json::from_str::<Input>(body) {
+        Ok(input) => {
+            let mut engine = engine.lock().unwrap();
+            match engine.apply(input) {
+                Ok(()) => Response::from_string("ok").with_status_code(200),
+                Err(err) => {
+                    error!("ledger error: {}", err);
+                    Response::from_string(err.to_string()).with_status_code(422)
+                }
+            }
+        }
+        Err(err) => {
+            error!("invalid transaction body: {}", err);
+            Response::from_string("invalid transaction body").with_status_code(400)
+        }
+    }
+}
+
+fn handle_get_account(engine: &Mutex<Engine>, client_id: Option<u16>) -> Response<Cursor<Vec<u8>>> {
+    let client_id = match client_id {
+        Some(client_id) => client_id,
+        None => return Response::from_string("invalid client id").with_status_code(400),
+    };
+
+    let engine = engine.lock().unwrap();
+    match engine.account_status(client_id) {
+        Some(status) => match serde_json::to_string(&status) {
+            Ok(json) => Response::from_string(json).with_status_code(200),
+            Err(err) => {
+                error!("failed to serialize account status: {}", err);
+                Response::from_string("internal error").with_status_code(500)
+            }
+        },
+        None => Response::from_string("unknown client").with_status_code(404),
+    }
+}
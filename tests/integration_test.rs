@@ -1,6 +1,7 @@
 extern crate transaction_engine;
 
 mod tests {
+    use rust_decimal::Decimal;
     use std::ffi::OsStr;
     use std::io::Cursor;
     use transaction_engine::*;
@@ -11,11 +12,222 @@ mod tests {
         let os_str = OsStr::new("tests/example0.csv");
         let cursor = Box::new(Cursor::new(Vec::new()));
 
-        if let Err(err) = process_file(os_str.to_os_string(), cursor) {
+        if let Err(err) = process_file(os_str.to_os_string(), cursor, &ProcessingContext::default())
+        {
             println!("{}", err);
             assert!(false);
         }
 
         // TODO: test the content of Cursor against the expected results
     }
+
+    fn deposit(client: &mut Client, transaction_id: u32, amount: Decimal) {
+        client.deposit(transaction_id, amount).unwrap();
+    }
+
+    #[test]
+    fn dispute_of_unknown_tx_returns_unknown_tx_error() {
+        let mut client = Client::new(1);
+
+        let err = client
+            .dispute(99, &ProcessingContext::default())
+            .unwrap_err();
+
+        assert_eq!(err, LedgerError::UnknownTx(1, 99));
+    }
+
+    #[test]
+    fn withdrawal_without_enough_funds_returns_not_enough_funds_error() {
+        let mut client = Client::new(1);
+
+        let err = client.withdrawal(1, Decimal::new(100, 0)).unwrap_err();
+
+        assert_eq!(err, LedgerError::NotEnoughFunds);
+    }
+
+    #[test]
+    fn double_dispute_returns_already_disputed_error() {
+        let mut client = Client::new(1);
+        deposit(&mut client, 1, Decimal::new(10, 0));
+
+        client.dispute(1, &ProcessingContext::default()).unwrap();
+
+        let err = client
+            .dispute(1, &ProcessingContext::default())
+            .unwrap_err();
+
+        assert_eq!(err, LedgerError::AlreadyDisputed);
+    }
+
+    #[test]
+    fn resolve_without_dispute_returns_not_disputed_error() {
+        let mut client = Client::new(1);
+        deposit(&mut client, 1, Decimal::new(10, 0));
+
+        let err = client
+            .resolve(1, &ProcessingContext::default())
+            .unwrap_err();
+
+        assert_eq!(err, LedgerError::NotDisputed);
+    }
+
+    #[test]
+    fn chargeback_after_resolve_returns_not_disputed_error() {
+        let mut client = Client::new(1);
+        deposit(&mut client, 1, Decimal::new(10, 0));
+
+        client.dispute(1, &ProcessingContext::default()).unwrap();
+        client.resolve(1, &ProcessingContext::default()).unwrap();
+
+        let err = client
+            .chargeback(1, &ProcessingContext::default())
+            .unwrap_err();
+
+        assert_eq!(err, LedgerError::NotDisputed);
+    }
+
+    #[test]
+    fn deposit_on_frozen_account_is_always_rejected() {
+        let mut client = Client::new(1);
+        client.account_status.locked = true;
+
+        let err = client.deposit(1, Decimal::new(10, 0)).unwrap_err();
+
+        assert_eq!(err, LedgerError::FrozenAccount);
+    }
+
+    #[test]
+    fn resolve_on_frozen_account_is_allowed_when_context_permits_it() {
+        let mut client = Client::new(1);
+        deposit(&mut client, 1, Decimal::new(10, 0));
+        client.dispute(1, &ProcessingContext::default()).unwrap();
+        client.account_status.locked = true;
+
+        let allowing = ProcessingContext {
+            allow_dispute_ops_when_frozen: true,
+            ..ProcessingContext::default()
+        };
+        client.resolve(1, &allowing).unwrap();
+    }
+
+    #[test]
+    fn resolve_on_frozen_account_is_rejected_when_context_forbids_it() {
+        let mut client = Client::new(1);
+        deposit(&mut client, 1, Decimal::new(10, 0));
+        client.dispute(1, &ProcessingContext::default()).unwrap();
+        client.account_status.locked = true;
+
+        let forbidding = ProcessingContext {
+            allow_dispute_ops_when_frozen: false,
+            ..ProcessingContext::default()
+        };
+        let err = client.resolve(1, &forbidding).unwrap_err();
+
+        assert_eq!(err, LedgerError::FrozenAccount);
+    }
+
+    #[test]
+    fn resolve_of_evicted_transaction_returns_unknown_tx_error() {
+        let mut client = Client::new(1);
+        deposit(&mut client, 1, Decimal::new(10, 0));
+
+        let evicting = ProcessingContext {
+            evict_terminal_transactions: true,
+            ..ProcessingContext::default()
+        };
+        client.dispute(1, &evicting).unwrap();
+        client.resolve(1, &evicting).unwrap();
+
+        let err = client.resolve(1, &evicting).unwrap_err();
+
+        assert_eq!(err, LedgerError::UnknownTx(1, 1));
+    }
+
+    fn input(operation: Operation, amount: Option<Decimal>) -> Input {
+        Input {
+            operation,
+            client_id: 1,
+            transaction_id: 1,
+            amount,
+        }
+    }
+
+    #[test]
+    fn deposit_input_without_amount_fails_to_parse() {
+        let err = Transaction::try_from(input(Operation::Deposit, None)).unwrap_err();
+        assert_eq!(err, LedgerError::MissingAmount);
+    }
+
+    #[test]
+    fn dispute_input_with_amount_fails_to_parse() {
+        let err =
+            Transaction::try_from(input(Operation::Dispute, Some(Decimal::new(1, 0)))).unwrap_err();
+        assert_eq!(err, LedgerError::UnexpectedAmount);
+    }
+
+    #[test]
+    fn deposit_input_with_amount_parses_to_deposit_transaction() {
+        let amount = Decimal::new(100, 2);
+        let transaction = Transaction::try_from(input(Operation::Deposit, Some(amount))).unwrap();
+
+        match transaction {
+            Transaction::Deposit {
+                transaction_id,
+                amount: parsed_amount,
+            } => {
+                assert_eq!(transaction_id, 1);
+                assert_eq!(parsed_amount, amount);
+            }
+            other => panic!("expected Transaction::Deposit, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn dispute_of_non_disputable_kind_returns_not_disputable_error() {
+        let mut client = Client::new(1);
+        deposit(&mut client, 1, Decimal::new(10, 0));
+
+        let context = ProcessingContext {
+            dispute_policy: DisputePolicy {
+                deposit_disputable: false,
+                ..DisputePolicy::default()
+            },
+            ..ProcessingContext::default()
+        };
+
+        let err = client.dispute(1, &context).unwrap_err();
+
+        assert_eq!(err, LedgerError::NotDisputable);
+    }
+
+    #[test]
+    fn dispute_that_would_go_negative_is_rejected_when_policy_forbids_it() {
+        let mut client = Client::new(1);
+        deposit(&mut client, 1, Decimal::new(10, 0));
+        client.withdrawal(2, Decimal::new(9, 0)).unwrap();
+
+        let context = ProcessingContext {
+            dispute_policy: DisputePolicy {
+                reject_negative_balances: true,
+                ..DisputePolicy::default()
+            },
+            ..ProcessingContext::default()
+        };
+
+        let err = client.dispute(1, &context).unwrap_err();
+
+        assert_eq!(err, LedgerError::NegativeBalance);
+    }
+
+    #[test]
+    fn dispute_that_would_go_negative_is_allowed_by_default() {
+        let mut client = Client::new(1);
+        deposit(&mut client, 1, Decimal::new(10, 0));
+        client.withdrawal(2, Decimal::new(9, 0)).unwrap();
+
+        client.dispute(1, &ProcessingContext::default()).unwrap();
+
+        assert_eq!(client.account_status.available, Decimal::new(-9, 0));
+        assert_eq!(client.account_status.held, Decimal::new(10, 0));
+    }
 }